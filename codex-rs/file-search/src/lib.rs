@@ -0,0 +1,258 @@
+//! Streaming fuzzy search over file paths under a directory.
+//!
+//! [`SearchManager`] owns a [`nucleo::Nucleo`] matcher fed by a background
+//! walker thread. Callers construct it with the query, then repeatedly call
+//! [`SearchManager::tick`] to let the matcher make progress and
+//! [`SearchManager::current_results`] to read back whatever matches are
+//! known so far.
+
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use ignore::WalkBuilder;
+use ignore::WalkState;
+use nucleo::Config;
+use nucleo::Injector;
+use nucleo::Nucleo;
+use nucleo::pattern::CaseMatching;
+use nucleo::pattern::Normalization;
+
+/// One candidate path pushed into the matcher.
+#[derive(Debug, Clone)]
+pub struct SearchItem {
+    pub path: String,
+}
+
+/// A path that matched the current query, with the byte indices (into
+/// `path`) that should be highlighted when `compute_indices` is set.
+#[derive(Debug, Clone)]
+pub struct FileMatch {
+    pub path: String,
+    pub indices: Vec<u32>,
+}
+
+/// Snapshot of whatever the matcher currently knows about.
+#[derive(Debug, Default, Clone)]
+pub struct SearchResults {
+    pub matches: Vec<FileMatch>,
+}
+
+#[derive(Debug)]
+pub enum SearchManagerError {
+    InvalidSearchDir(PathBuf),
+}
+
+impl fmt::Display for SearchManagerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSearchDir(path) => {
+                write!(f, "search directory does not exist: {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SearchManagerError {}
+
+pub struct SearchManager {
+    nucleo: Nucleo<SearchItem>,
+    limit: NonZeroUsize,
+    compute_indices: bool,
+    matcher: nucleo::Matcher,
+    cancel_walk: Arc<AtomicBool>,
+    files_scanned: Arc<AtomicUsize>,
+}
+
+impl SearchManager {
+    pub fn new(
+        query: &str,
+        limit: NonZeroUsize,
+        search_dir: &Path,
+        exclude: Vec<String>,
+        threads: NonZeroUsize,
+        respect_gitignore: bool,
+        compute_indices: bool,
+        notify: Arc<dyn Fn() + Send + Sync>,
+    ) -> Result<Self, SearchManagerError> {
+        if !search_dir.exists() {
+            return Err(SearchManagerError::InvalidSearchDir(search_dir.to_path_buf()));
+        }
+
+        // Split on whitespace into atoms (e.g. `src/ !test $.rs`) and give
+        // each its own pattern column, matched against the same path text.
+        // `nucleo::Nucleo` ANDs per-column scores, so every atom must match
+        // for a path to surface; a leading `'`, `^`, or `!` within an atom
+        // is parsed by nucleo itself into exact/anchored/negated matching.
+        // nucleo only reads a *trailing* `$` as an end anchor, so a leading
+        // `$` (after an optional `!`) is moved to the end: `$.rs` -> `.rs$`.
+        let atoms: Vec<&str> = query.split_whitespace().collect();
+        let num_columns = atoms.len().max(1) as u32;
+
+        let mut nucleo = Nucleo::new(Config::DEFAULT, notify, Some(threads.get()), num_columns);
+        for (column, atom) in atoms.iter().enumerate() {
+            let (negate, rest) = match atom.strip_prefix('!') {
+                Some(rest) => ("!", rest),
+                None => ("", *atom),
+            };
+            let atom = match rest.strip_prefix('$') {
+                Some(body) if !body.is_empty() => format!("{negate}{body}$"),
+                _ => (*atom).to_string(),
+            };
+            nucleo.pattern.reparse(
+                column,
+                &atom,
+                CaseMatching::Smart,
+                Normalization::Smart,
+                false,
+            );
+        }
+
+        let cancel_walk = Arc::new(AtomicBool::new(false));
+        let files_scanned = Arc::new(AtomicUsize::new(0));
+        spawn_walk(
+            search_dir.to_path_buf(),
+            exclude,
+            respect_gitignore,
+            threads,
+            num_columns,
+            nucleo.injector(),
+            cancel_walk.clone(),
+            files_scanned.clone(),
+        );
+
+        Ok(Self {
+            nucleo,
+            limit,
+            compute_indices,
+            matcher: nucleo::Matcher::new(Config::DEFAULT),
+            cancel_walk,
+            files_scanned,
+        })
+    }
+
+    /// Handle used by the walker (or, in tests, directly by the caller) to
+    /// push candidate paths as they're discovered.
+    pub fn injector(&self) -> Injector<SearchItem> {
+        self.nucleo.injector()
+    }
+
+    /// Stop the background walk as soon as it next checks in. In-flight
+    /// matches already pushed to the matcher are kept.
+    pub fn cancel(&self) {
+        self.cancel_walk.store(true, Ordering::Relaxed);
+    }
+
+    /// How many directory entries the walker has visited so far, regardless
+    /// of whether they matched the query. Useful for surfacing walk progress
+    /// on large trees.
+    pub fn files_scanned(&self) -> usize {
+        self.files_scanned.load(Ordering::Relaxed)
+    }
+
+    /// Give the matcher up to `timeout` to process pushed paths and report
+    /// whether anything changed and whether matching is still running. The
+    /// walker runs on its own thread; `running` does not say whether it has
+    /// finished walking.
+    pub fn tick(&mut self, timeout: Duration) -> nucleo::Status {
+        self.nucleo.tick(timeout.as_millis() as u64)
+    }
+
+    /// The best `limit` matches known so far, most relevant first.
+    pub fn current_results(&mut self) -> SearchResults {
+        let snapshot = self.nucleo.snapshot();
+        let count = (snapshot.matched_item_count() as usize).min(self.limit.get()) as u32;
+
+        let mut matches = Vec::with_capacity(count as usize);
+        for item in snapshot.matched_items(0..count) {
+            let mut indices = Vec::new();
+            if self.compute_indices {
+                // Every column holds the same path text (see `new`), so
+                // merge the highlight ranges each atom contributed.
+                for column in 0..item.matcher_columns.len() {
+                    snapshot.pattern().column_pattern(column).indices(
+                        item.matcher_columns[column].slice(..),
+                        &mut self.matcher,
+                        &mut indices,
+                    );
+                }
+                indices.sort_unstable();
+                indices.dedup();
+            }
+            matches.push(FileMatch {
+                path: item.data.path.clone(),
+                indices,
+            });
+        }
+
+        SearchResults { matches }
+    }
+}
+
+/// Walks `search_dir` in parallel, pushing every file whose path doesn't
+/// contain an `exclude` needle into `injector`. Honors `.gitignore`/`.ignore`
+/// (including the global gitignore and hidden-file rules) when
+/// `respect_gitignore` is set; returns `WalkState::Quit` as soon as `cancel`
+/// flips so a superseded search stops walking promptly.
+fn spawn_walk(
+    search_dir: PathBuf,
+    exclude: Vec<String>,
+    respect_gitignore: bool,
+    threads: NonZeroUsize,
+    num_columns: u32,
+    injector: Injector<SearchItem>,
+    cancel: Arc<AtomicBool>,
+    files_scanned: Arc<AtomicUsize>,
+) {
+    std::thread::spawn(move || {
+        let mut builder = WalkBuilder::new(&search_dir);
+        builder
+            .hidden(respect_gitignore)
+            .ignore(respect_gitignore)
+            .git_ignore(respect_gitignore)
+            .git_global(respect_gitignore)
+            .git_exclude(respect_gitignore)
+            .require_git(false)
+            .threads(threads.get());
+
+        let exclude = Arc::new(exclude);
+        builder.build_parallel().run(|| {
+            let injector = injector.clone();
+            let cancel = cancel.clone();
+            let exclude = exclude.clone();
+            let files_scanned = files_scanned.clone();
+            Box::new(move |entry| {
+                if cancel.load(Ordering::Relaxed) {
+                    return WalkState::Quit;
+                }
+
+                let Ok(entry) = entry else {
+                    return WalkState::Continue;
+                };
+                files_scanned.fetch_add(1, Ordering::Relaxed);
+                if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                    return WalkState::Continue;
+                }
+
+                let path = entry.path().to_string_lossy().into_owned();
+                if exclude.iter().any(|needle| path.contains(needle.as_str())) {
+                    return WalkState::Continue;
+                }
+
+                injector.push(SearchItem { path }, |item, columns| {
+                    for column in &mut columns[..num_columns as usize] {
+                        *column = item.path.as_str().into();
+                    }
+                });
+
+                WalkState::Continue
+            })
+        });
+    });
+}
@@ -13,7 +13,12 @@ fn push(injector: &nucleo::Injector<SearchItem>, path: &str) {
             path: path.to_string(),
         },
         |item, columns| {
-            columns[0] = item.path.as_str().into();
+            // Mirrors `spawn_walk` in lib.rs: every column holds the same
+            // path text, since each whitespace-separated query atom gets its
+            // own column and all of them are matched against the full path.
+            for column in columns.iter_mut() {
+                *column = item.path.as_str().into();
+            }
         },
     );
 }
@@ -37,6 +42,7 @@ fn search_manager_streams_results() {
         temp_dir.path(),
         Vec::new(),
         threads,
+        true,
         false,
         notify,
     )
@@ -96,6 +102,7 @@ fn search_manager_walk_finds_files() {
         Vec::new(),
         threads,
         true,
+        true,
         notify,
     )
     .unwrap();
@@ -130,3 +137,109 @@ fn search_manager_walk_finds_files() {
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn search_manager_respects_gitignore() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join(".gitignore"), "target/\n").unwrap();
+    let ignored_dir = temp_dir.path().join("target");
+    std::fs::create_dir_all(&ignored_dir).unwrap();
+    std::fs::write(ignored_dir.join("gamma.rs"), "fn main() {}").unwrap();
+
+    let limit = NonZeroUsize::new(10).unwrap();
+    let threads = NonZeroUsize::new(1).unwrap();
+
+    let wait_for_idle = |manager: &mut SearchManager| {
+        let start = std::time::Instant::now();
+        loop {
+            let status = manager.tick(Duration::from_millis(20));
+            if !status.running {
+                break;
+            }
+            if start.elapsed() > Duration::from_secs(5) {
+                break;
+            }
+        }
+    };
+
+    let notify = Arc::new(|| {});
+    let mut respecting = SearchManager::new(
+        "gam",
+        limit,
+        temp_dir.path(),
+        Vec::new(),
+        threads,
+        true,
+        true,
+        notify.clone(),
+    )
+    .unwrap();
+    wait_for_idle(&mut respecting);
+    assert!(
+        !respecting
+            .current_results()
+            .matches
+            .iter()
+            .any(|m| m.path.ends_with("gamma.rs")),
+        "gitignored target/gamma.rs should not be surfaced when respect_gitignore is set"
+    );
+
+    let mut ignoring = SearchManager::new(
+        "gam", limit, temp_dir.path(), Vec::new(), threads, false, true, notify,
+    )
+    .unwrap();
+    wait_for_idle(&mut ignoring);
+    assert!(
+        ignoring
+            .current_results()
+            .matches
+            .iter()
+            .any(|m| m.path.ends_with("gamma.rs")),
+        "target/gamma.rs should be found when respect_gitignore is disabled"
+    );
+}
+
+#[test]
+fn search_manager_supports_multi_atom_query_syntax() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let notify = Arc::new(|| {});
+    let limit = NonZeroUsize::new(10).unwrap();
+    let threads = NonZeroUsize::new(2).unwrap();
+
+    // "src/ !test $.rs" means: under src/, ending in .rs, excluding test
+    // paths. nucleo splits this on whitespace into AND-ed atoms, with `!`
+    // negating an atom and `$` anchoring it to the end of the path.
+    let mut manager = SearchManager::new(
+        "src/ !test $.rs",
+        limit,
+        temp_dir.path(),
+        Vec::new(),
+        threads,
+        false,
+        false,
+        notify,
+    )
+    .unwrap();
+
+    let injector = manager.injector();
+    push(&injector, "src/lib.rs");
+    push(&injector, "src/tests/lib.rs");
+    push(&injector, "src/lib.txt");
+    push(&injector, "other/lib.rs");
+
+    for _ in 0..50 {
+        let status = manager.tick(Duration::from_millis(10));
+        if !status.running {
+            break;
+        }
+    }
+
+    let paths: Vec<String> = manager
+        .current_results()
+        .matches
+        .iter()
+        .map(|m| m.path.clone())
+        .collect();
+    assert_eq!(paths, vec!["src/lib.rs".to_string()], "paths={paths:?}");
+}
@@ -19,7 +19,14 @@
 //!    the user typed, it is cancelled.
 
 use codex_file_search as file_search;
+use grep_matcher::Matcher;
+use grep_regex::RegexMatcher;
+use grep_searcher::Searcher;
+use grep_searcher::Sink;
+use grep_searcher::SinkMatch;
+use std::io;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -35,6 +42,20 @@ use crate::app_event_sender::AppEventSender;
 const MAX_FILE_SEARCH_RESULTS: NonZeroUsize = NonZeroUsize::new(8).unwrap();
 const NUM_FILE_SEARCH_THREADS: NonZeroUsize = NonZeroUsize::new(2).unwrap();
 
+/// Cap on how many content matches we collect for a single `@:` query before
+/// we stop walking. Keeps a query like `@:e` from scanning gigabytes of text.
+const MAX_CONTENT_SEARCH_RESULTS: usize = 200;
+
+/// A single line in a file that matched a content-search query, along with
+/// the byte ranges (within `line`) that should be highlighted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ContentMatch {
+    pub path: PathBuf,
+    pub line_number: u64,
+    pub line: String,
+    pub submatches: Vec<(usize, usize)>,
+}
+
 /// How long to wait after a keystroke before firing the first search when none
 /// is currently running. Keeps early queries more meaningful.
 const FILE_SEARCH_DEBOUNCE: Duration = Duration::from_millis(100);
@@ -43,6 +64,11 @@ const ACTIVE_SEARCH_COMPLETE_POLL_INTERVAL: Duration = Duration::from_millis(20)
 const SEARCH_MANAGER_TICK_TIMEOUT: Duration = Duration::from_millis(16);
 const SEARCH_MANAGER_FIRST_RESULT_TIMEOUT: Duration = Duration::from_millis(200);
 
+/// How often to emit `AppEvent::FileSearchProgress` while a path search is
+/// walking a large tree, so the composer can show "scanned N files" instead
+/// of appearing frozen.
+const FILE_SEARCH_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
 /// State machine for file-search orchestration.
 pub(crate) struct FileSearchManager {
     /// Unified state guarded by one mutex.
@@ -50,6 +76,15 @@ pub(crate) struct FileSearchManager {
 
     search_dir: PathBuf,
     app_tx: AppEventSender,
+
+    /// Whether the path-fuzzy walk should honor `.gitignore`/`.ignore` rules.
+    /// Disabled for users who explicitly want to see everything under
+    /// `search_dir`, including build artifacts and VCS-ignored files.
+    respect_gitignore: bool,
+
+    /// Monotonic counter backing [`SearchId`]s handed out by `start_search`
+    /// and `start_content_search`.
+    next_search_id: std::sync::atomic::AtomicU64,
 }
 
 #[cfg(test)]
@@ -103,21 +138,174 @@ mod tests {
             "file search did not emit expected result; captured events: {captured:?}"
         );
     }
+
+    #[test]
+    fn file_search_emits_progress() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("gamma.rs"), "fn main() {}").unwrap();
+
+        let (tx, mut rx) = unbounded_channel();
+        let manager =
+            FileSearchManager::new(temp_dir.path().to_path_buf(), AppEventSender::new(tx));
+        manager.on_user_query("gam".to_string());
+
+        let start = Instant::now();
+        let mut saw_progress = false;
+        let mut captured: Vec<String> = Vec::new();
+
+        while start.elapsed() < Duration::from_secs(2) {
+            while let Ok(event) = rx.try_recv() {
+                if matches!(&event, AppEvent::FileSearchProgress { .. }) {
+                    saw_progress = true;
+                }
+                captured.push(format!("{event:?}"));
+            }
+            if saw_progress {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(
+            saw_progress,
+            "expected at least one FileSearchProgress event; captured events: {captured:?}"
+        );
+    }
+
+    #[test]
+    fn content_search_emits_matching_line() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "alpha\nneedle here\nbeta\n").unwrap();
+
+        let (tx, mut rx) = unbounded_channel();
+        let manager =
+            FileSearchManager::new(temp_dir.path().to_path_buf(), AppEventSender::new(tx));
+        manager.on_user_content_query("needle".to_string());
+
+        let start = Instant::now();
+        let mut saw_match = false;
+        let mut captured: Vec<String> = Vec::new();
+
+        while start.elapsed() < Duration::from_secs(2) {
+            while let Ok(event) = rx.try_recv() {
+                if let AppEvent::ContentSearchResult { matches, .. } = &event
+                    && matches.iter().any(|m| m.line.contains("needle"))
+                {
+                    saw_match = true;
+                }
+                captured.push(format!("{event:?}"));
+            }
+            if saw_match {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(
+            saw_match,
+            "content search did not emit expected match; captured events: {captured:?}"
+        );
+    }
+
+    /// Cancelling a search while it is only *scheduled* (debounce still
+    /// pending, no `ActiveSearch` registered) must stop it from ever
+    /// dispatching -- not just blank the query it would have run with.
+    #[test]
+    fn cancel_search_prevents_stale_dispatch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("gamma.rs"), "fn main() {}").unwrap();
+
+        let (tx, mut rx) = unbounded_channel();
+        let manager =
+            FileSearchManager::new(temp_dir.path().to_path_buf(), AppEventSender::new(tx));
+
+        let id = manager.start_search("gam".to_string());
+        manager.cancel_search(id);
+
+        let start = Instant::now();
+        let mut captured: Vec<String> = Vec::new();
+        while start.elapsed() < Duration::from_millis(500) {
+            while let Ok(event) = rx.try_recv() {
+                captured.push(format!("{event:?}"));
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(
+            captured.is_empty(),
+            "cancelled-while-scheduled search must not dispatch; captured events: {captured:?}"
+        );
+    }
+
+    #[test]
+    fn cancel_all_stops_pending_search() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("gamma.rs"), "fn main() {}").unwrap();
+
+        let (tx, mut rx) = unbounded_channel();
+        let manager =
+            FileSearchManager::new(temp_dir.path().to_path_buf(), AppEventSender::new(tx));
+
+        manager.start_search("gam".to_string());
+        manager.cancel_all();
+
+        let start = Instant::now();
+        let mut captured: Vec<String> = Vec::new();
+        while start.elapsed() < Duration::from_millis(500) {
+            while let Ok(event) = rx.try_recv() {
+                captured.push(format!("{event:?}"));
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(
+            captured.is_empty(),
+            "cancel_all must stop a pending search; captured events: {captured:?}"
+        );
+    }
 }
 
+/// Which backend a query should be routed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchKind {
+    /// Fuzzy-match against file paths (the classic `@` behavior).
+    Path,
+    /// Grep file contents for a regex (the `@:` behavior).
+    Content,
+}
+
+/// Identifies one logical search request made through [`FileSearchManager`].
+/// Lets a caller tell a late result from a superseded search apart from the
+/// current one, instead of matching only by query-string prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct SearchId(u64);
+
 struct SearchState {
     /// Latest query typed by user (updated every keystroke).
     latest_query: String,
 
+    /// Kind of search the latest query should run as.
+    latest_kind: SearchKind,
+
+    /// Id of the most recently requested search.
+    latest_id: SearchId,
+
     /// true if a search is currently scheduled.
     is_search_scheduled: bool,
 
+    /// Set when `latest_id` has been cancelled while only scheduled (no
+    /// `ActiveSearch` yet). Tells the pending debounce thread to skip
+    /// dispatch instead of firing a stale, supposedly-cancelled search.
+    cancelled: bool,
+
     /// If there is an active search, this will be the query being searched.
     active_search: Option<ActiveSearch>,
 }
 
 struct ActiveSearch {
+    id: SearchId,
     query: String,
+    kind: SearchKind,
     cancellation_token: Arc<AtomicBool>,
 }
 
@@ -146,35 +334,124 @@ impl Drop for ActiveSearchGuard {
 
 impl FileSearchManager {
     pub fn new(search_dir: PathBuf, tx: AppEventSender) -> Self {
+        Self::with_gitignore(search_dir, tx, true)
+    }
+
+    /// Like [`FileSearchManager::new`], but lets the caller opt out of
+    /// `.gitignore`/`.ignore` filtering for both the path-fuzzy and
+    /// content-grep walks.
+    pub fn with_gitignore(search_dir: PathBuf, tx: AppEventSender, respect_gitignore: bool) -> Self {
         Self {
             state: Arc::new(Mutex::new(SearchState {
                 latest_query: String::new(),
+                latest_kind: SearchKind::Path,
+                latest_id: SearchId(0),
                 is_search_scheduled: false,
+                cancelled: false,
                 active_search: None,
             })),
             search_dir,
             app_tx: tx,
+            respect_gitignore,
+            next_search_id: std::sync::atomic::AtomicU64::new(1),
         }
     }
 
     /// Call whenever the user edits the `@` token.
+    ///
+    /// `query` is split on whitespace into atoms and handed to
+    /// [`file_search::SearchManager`] as-is, so the caller does not need to
+    /// pre-parse nucleo's pattern syntax: a leading `'` forces an exact
+    /// (non-fuzzy) match, `^`/`$` anchor to the start/end of the path, and a
+    /// leading `!` negates an atom. For example `src/ !test $.rs` means
+    /// "under src/, ending in .rs, excluding anything with test".
     pub fn on_user_query(&self, query: String) {
+        self.start_search(query);
+    }
+
+    /// Call whenever the user edits the `@:` (content search) token.
+    pub fn on_user_content_query(&self, query: String) {
+        self.start_content_search(query);
+    }
+
+    /// Explicit, id-addressable entry point for a path-fuzzy search. Returns
+    /// a [`SearchId`] the caller can later pass to [`Self::cancel_search`].
+    pub fn start_search(&self, query: String) -> SearchId {
+        self.request_search(query, SearchKind::Path)
+    }
+
+    /// Explicit, id-addressable entry point for a content-grep search.
+    pub fn start_content_search(&self, query: String) -> SearchId {
+        self.request_search(query, SearchKind::Content)
+    }
+
+    /// Cancel `id` if it is the latest or the currently active search. A
+    /// caller can use this on focus loss, on ESC, or when the `@` token is
+    /// deleted entirely, instead of relying on the next query to be a
+    /// non-prefix of the active one.
+    pub fn cancel_search(&self, id: SearchId) {
+        #[expect(clippy::unwrap_used)]
+        let mut st = self.state.lock().unwrap();
+        if let Some(active_search) = &st.active_search
+            && active_search.id == id
+        {
+            active_search
+                .cancellation_token
+                .store(true, Ordering::Relaxed);
+            st.active_search = None;
+        }
+        if st.latest_id == id {
+            st.latest_query.clear();
+            // The active search (if any) was just handled above, but a
+            // *scheduled* search for this id may still be waiting on the
+            // debounce timer with no `ActiveSearch` registered yet. Flag it
+            // so the debounce thread skips dispatch instead of firing a
+            // "match everything" search tagged with this cancelled id.
+            if st.is_search_scheduled {
+                st.cancelled = true;
+            }
+        }
+    }
+
+    /// Cancel whatever search is active or scheduled, regardless of id.
+    pub fn cancel_all(&self) {
+        #[expect(clippy::unwrap_used)]
+        let mut st = self.state.lock().unwrap();
+        if let Some(active_search) = &st.active_search {
+            active_search
+                .cancellation_token
+                .store(true, Ordering::Relaxed);
+        }
+        st.active_search = None;
+        st.latest_query.clear();
+        if st.is_search_scheduled {
+            st.cancelled = true;
+        }
+    }
+
+    fn request_search(&self, query: String, kind: SearchKind) -> SearchId {
+        let id;
         {
             #[expect(clippy::unwrap_used)]
             let mut st = self.state.lock().unwrap();
-            if query == st.latest_query {
+            if query == st.latest_query && kind == st.latest_kind {
                 // No change, nothing to do.
-                return;
+                return st.latest_id;
             }
 
+            id = SearchId(self.next_search_id.fetch_add(1, Ordering::Relaxed));
+
             // Update latest query.
             st.latest_query.clear();
             st.latest_query.push_str(&query);
+            st.latest_kind = kind;
+            st.latest_id = id;
+            st.cancelled = false;
 
             // If there is an in-flight search that is definitely obsolete,
             // cancel it now.
             if let Some(active_search) = &st.active_search
-                && !query.starts_with(&active_search.query)
+                && (active_search.kind != kind || !query.starts_with(&active_search.query))
             {
                 active_search
                     .cancellation_token
@@ -186,7 +463,7 @@ impl FileSearchManager {
             if !st.is_search_scheduled {
                 st.is_search_scheduled = true;
             } else {
-                return;
+                return id;
             }
         }
 
@@ -196,6 +473,7 @@ impl FileSearchManager {
         let state = self.state.clone();
         let search_dir = self.search_dir.clone();
         let tx_clone = self.app_tx.clone();
+        let respect_gitignore = self.respect_gitignore;
         thread::spawn(move || {
             // Always do a minimum debounce, but then poll until the
             // `active_search` is cleared.
@@ -209,37 +487,69 @@ impl FileSearchManager {
             }
 
             // The debounce timer has expired, so start a search using the
-            // latest query.
+            // latest query (which may have moved on since this request, but
+            // is still tagged with its own id) -- unless it was cancelled
+            // while still only scheduled, in which case there is nothing to
+            // dispatch.
             let cancellation_token = Arc::new(AtomicBool::new(false));
             let token = cancellation_token.clone();
-            let query = {
+            let dispatch = {
                 #[expect(clippy::unwrap_used)]
                 let mut st = state.lock().unwrap();
-                let query = st.latest_query.clone();
                 st.is_search_scheduled = false;
-                st.active_search = Some(ActiveSearch {
-                    query: query.clone(),
-                    cancellation_token: token,
-                });
-                query
+                if st.cancelled {
+                    st.cancelled = false;
+                    None
+                } else {
+                    let id = st.latest_id;
+                    let query = st.latest_query.clone();
+                    let kind = st.latest_kind;
+                    st.active_search = Some(ActiveSearch {
+                        id,
+                        query: query.clone(),
+                        kind,
+                        cancellation_token: token,
+                    });
+                    Some((id, query, kind))
+                }
+            };
+            let Some((id, query, kind)) = dispatch else {
+                return;
             };
 
-            FileSearchManager::spawn_file_search(
-                query,
-                search_dir,
-                tx_clone,
-                cancellation_token,
-                state,
-            );
+            match kind {
+                SearchKind::Path => FileSearchManager::spawn_file_search(
+                    id,
+                    query,
+                    search_dir,
+                    tx_clone,
+                    cancellation_token,
+                    state,
+                    respect_gitignore,
+                ),
+                SearchKind::Content => FileSearchManager::spawn_content_search(
+                    id,
+                    query,
+                    search_dir,
+                    tx_clone,
+                    cancellation_token,
+                    state,
+                    respect_gitignore,
+                ),
+            }
         });
+
+        id
     }
 
     fn spawn_file_search(
+        id: SearchId,
         query: String,
         search_dir: PathBuf,
         tx: AppEventSender,
         cancellation_token: Arc<AtomicBool>,
         search_state: Arc<Mutex<SearchState>>,
+        respect_gitignore: bool,
     ) {
         let compute_indices = true;
         std::thread::spawn(move || {
@@ -258,6 +568,7 @@ impl FileSearchManager {
                 &search_dir,
                 Vec::new(),
                 NUM_FILE_SEARCH_THREADS,
+                respect_gitignore,
                 compute_indices,
                 notify,
             ) {
@@ -265,6 +576,7 @@ impl FileSearchManager {
                 Err(err) => {
                     tracing::error!("file search initialization failed: {err:?}");
                     tx.send(AppEvent::FileSearchResult {
+                        id,
                         query: query.clone(),
                         matches: Vec::new(),
                     });
@@ -276,6 +588,7 @@ impl FileSearchManager {
             let mut sent_once = false;
             let start = Instant::now();
             let mut last_progress = start;
+            let mut last_progress_event = start;
 
             loop {
                 if cancellation_token.load(Ordering::Relaxed) {
@@ -283,6 +596,20 @@ impl FileSearchManager {
                 }
 
                 let status = manager.tick(SEARCH_MANAGER_TICK_TIMEOUT);
+
+                if !cancellation_token.load(Ordering::Relaxed)
+                    && (last_progress_event.elapsed() >= FILE_SEARCH_PROGRESS_INTERVAL
+                        || !status.running)
+                {
+                    tx.send(AppEvent::FileSearchProgress {
+                        id,
+                        query: query.clone(),
+                        scanned: manager.files_scanned(),
+                        done: !status.running,
+                    });
+                    last_progress_event = Instant::now();
+                }
+
                 let flag_was_set = notify_flag.swap(false, Ordering::AcqRel);
                 let results = manager.current_results();
                 let matches = results.matches;
@@ -301,6 +628,7 @@ impl FileSearchManager {
 
                 if should_emit {
                     tx.send(AppEvent::FileSearchResult {
+                        id,
                         query: query.clone(),
                         matches: matches.clone(),
                     });
@@ -320,6 +648,7 @@ impl FileSearchManager {
                         }
                     } else if timeout_elapsed {
                         tx.send(AppEvent::FileSearchResult {
+                            id,
                             query: query.clone(),
                             matches,
                         });
@@ -329,4 +658,127 @@ impl FileSearchManager {
             }
         });
     }
+
+    fn spawn_content_search(
+        id: SearchId,
+        query: String,
+        search_dir: PathBuf,
+        tx: AppEventSender,
+        cancellation_token: Arc<AtomicBool>,
+        search_state: Arc<Mutex<SearchState>>,
+        respect_gitignore: bool,
+    ) {
+        std::thread::spawn(move || {
+            let _guard = ActiveSearchGuard::new(search_state, cancellation_token.clone());
+
+            let matcher = match RegexMatcher::new(&query) {
+                Ok(matcher) => matcher,
+                Err(err) => {
+                    tracing::error!("content search regex build failed: {err:?}");
+                    tx.send(AppEvent::ContentSearchResult {
+                        id,
+                        query,
+                        matches: Vec::new(),
+                    });
+                    return;
+                }
+            };
+
+            // Honor the same .gitignore/.ignore setting as the path-fuzzy
+            // walk, so toggling it affects `@:` content results too.
+            let walk = ignore::WalkBuilder::new(&search_dir)
+                .hidden(respect_gitignore)
+                .ignore(respect_gitignore)
+                .git_ignore(respect_gitignore)
+                .git_global(respect_gitignore)
+                .git_exclude(respect_gitignore)
+                .require_git(false)
+                .build();
+
+            let mut matches: Vec<ContentMatch> = Vec::new();
+            let mut sent_once = false;
+            let mut last_sent_len = 0;
+            for entry in walk {
+                if cancellation_token.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(entry) = entry else { continue };
+                if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                    continue;
+                }
+
+                let mut sink = ContentSearchSink {
+                    path: entry.path(),
+                    matcher: &matcher,
+                    cancellation_token: &cancellation_token,
+                    matches: &mut matches,
+                    limit: MAX_CONTENT_SEARCH_RESULTS,
+                };
+                if let Err(err) = Searcher::new().search_path(&matcher, entry.path(), &mut sink) {
+                    tracing::debug!("content search skipped {:?}: {err}", entry.path());
+                }
+
+                if matches.len() != last_sent_len {
+                    tx.send(AppEvent::ContentSearchResult {
+                        id,
+                        query: query.clone(),
+                        matches: matches.clone(),
+                    });
+                    sent_once = true;
+                    last_sent_len = matches.len();
+                }
+
+                if matches.len() >= MAX_CONTENT_SEARCH_RESULTS {
+                    break;
+                }
+            }
+
+            // Only send a closing event when we never sent one in the loop
+            // (e.g. zero matches); otherwise the last in-loop send already
+            // reflects the final state and this would just be a duplicate.
+            if !cancellation_token.load(Ordering::Relaxed) && !sent_once {
+                tx.send(AppEvent::ContentSearchResult { id, query, matches });
+            }
+        });
+    }
+}
+
+/// Feeds matched lines from a single file, interrupted by `cancellation_token`,
+/// into `matches`.
+struct ContentSearchSink<'a> {
+    path: &'a Path,
+    matcher: &'a RegexMatcher,
+    cancellation_token: &'a AtomicBool,
+    matches: &'a mut Vec<ContentMatch>,
+    limit: usize,
+}
+
+impl Sink for ContentSearchSink<'_> {
+    type Error = io::Error;
+
+    fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> Result<bool, io::Error> {
+        if self.cancellation_token.load(Ordering::Relaxed) || self.matches.len() >= self.limit {
+            return Ok(false);
+        }
+
+        let bytes = mat.bytes();
+        let line = String::from_utf8_lossy(bytes)
+            .trim_end_matches(['\n', '\r'])
+            .to_string();
+
+        let mut submatches = Vec::new();
+        let _ = self.matcher.find_iter(bytes, |m| {
+            submatches.push((m.start(), m.end()));
+            true
+        });
+
+        self.matches.push(ContentMatch {
+            path: self.path.to_path_buf(),
+            line_number: mat.line_number().unwrap_or(0),
+            line,
+            submatches,
+        });
+
+        Ok(self.matches.len() < self.limit && !self.cancellation_token.load(Ordering::Relaxed))
+    }
 }